@@ -0,0 +1,35 @@
+//! `SecretBackend` skeleton driving the official Bitwarden `bw` CLI.
+//!
+//! Unlike `rbw`, `bw` requires an explicit `bw unlock`/`bw login` dance and
+//! returns a session key that must be threaded through every subsequent
+//! call (`--session` or `BW_SESSION`). None of that is wired up yet — this
+//! exists so `--backend bw` resolves to something rather than nothing, and
+//! so the real implementation has a home to land in.
+
+use super::SecretBackend;
+use anyhow::{Result, bail};
+
+/// Drives the official `bw` CLI. Not yet implemented.
+pub struct BwBackend;
+
+impl SecretBackend for BwBackend {
+    fn list_namespaces(&self, _folder: &str) -> Result<Vec<String>> {
+        bail!("the `bw` backend is not implemented yet; use `--backend rbw` or `--backend pass`")
+    }
+
+    fn get_item(&self, _name: &str, _folder: &str) -> Result<Option<String>> {
+        bail!("the `bw` backend is not implemented yet; use `--backend rbw` or `--backend pass`")
+    }
+
+    fn create_item(&self, _name: &str, _folder: &str, _notes_content: &str) -> Result<()> {
+        bail!("the `bw` backend is not implemented yet; use `--backend rbw` or `--backend pass`")
+    }
+
+    fn edit_item(&self, _name: &str, _folder: &str, _notes_content: &str) -> Result<()> {
+        bail!("the `bw` backend is not implemented yet; use `--backend rbw` or `--backend pass`")
+    }
+
+    fn delete_item(&self, _name: &str, _folder: &str) -> Result<()> {
+        bail!("the `bw` backend is not implemented yet; use `--backend rbw` or `--backend pass`")
+    }
+}
@@ -0,0 +1,53 @@
+//! Pluggable secret-store backends.
+//!
+//! `envrbw` talks to a password manager through the `SecretBackend` trait so
+//! that `rbw` is one implementation among several, not a hard dependency.
+//! Select one with `--backend`/`ENVRBW_BACKEND` (default: `rbw`).
+
+mod bw;
+mod pass;
+mod rbw;
+
+use anyhow::{Result, bail};
+
+pub const DEFAULT_BACKEND: &str = "rbw";
+pub const BACKEND_ENV: &str = "ENVRBW_BACKEND";
+
+/// A secret store that can list, read, and write envrbw namespaces.
+///
+/// A namespace is identified by its `name` within a `folder` (the grouping
+/// concept `folder` maps to — a Bitwarden folder, a `pass` subdirectory,
+/// etc.). An item's content is its raw KEY=VALUE note text; parsing it is
+/// `store`'s job, not the backend's.
+pub trait SecretBackend {
+    /// List namespace names within `folder`.
+    fn list_namespaces(&self, folder: &str) -> Result<Vec<String>>;
+
+    /// Fetch a namespace's raw note content. Returns `None` if it doesn't exist.
+    fn get_item(&self, name: &str, folder: &str) -> Result<Option<String>>;
+
+    /// Create a new namespace with `notes_content` in `folder`.
+    fn create_item(&self, name: &str, folder: &str, notes_content: &str) -> Result<()>;
+
+    /// Replace an existing namespace's content with `notes_content`.
+    fn edit_item(&self, name: &str, folder: &str, notes_content: &str) -> Result<()>;
+
+    /// Delete a namespace.
+    fn delete_item(&self, name: &str, folder: &str) -> Result<()>;
+}
+
+/// Resolve the backend: CLI flag > alias entry > env var > default.
+pub fn resolve(cli_backend: Option<&str>, alias_backend: Option<&str>) -> Result<Box<dyn SecretBackend>> {
+    let name = cli_backend
+        .map(str::to_string)
+        .or_else(|| alias_backend.map(str::to_string))
+        .or_else(|| std::env::var(BACKEND_ENV).ok())
+        .unwrap_or_else(|| DEFAULT_BACKEND.to_string());
+
+    match name.as_str() {
+        "rbw" => Ok(Box::new(rbw::RbwBackend)),
+        "pass" => Ok(Box::new(pass::PassBackend)),
+        "bw" => Ok(Box::new(bw::BwBackend)),
+        other => bail!("unknown backend `{other}` (expected one of: rbw, pass, bw)"),
+    }
+}
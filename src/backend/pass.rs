@@ -0,0 +1,111 @@
+//! `SecretBackend` implementation driving the standard `pass` CLI
+//! (<https://www.passwordstore.org/>).
+//!
+//! A namespace is a `pass` entry living under `folder/`, e.g. folder
+//! `envrbw` and namespace `myns` map to the store path `envrbw/myns`. The
+//! entry's multiline body *is* the KEY=VALUE note — there is no separate
+//! fields concept to fall back to like rbw's envwarden compatibility shim.
+
+use super::SecretBackend;
+use anyhow::{Context, Result, bail};
+use std::io::Write as _;
+use std::process::{Command, Stdio};
+
+/// Drives the `pass` CLI.
+pub struct PassBackend;
+
+impl SecretBackend for PassBackend {
+    fn list_namespaces(&self, folder: &str) -> Result<Vec<String>> {
+        let output = Command::new("pass")
+            .args(["ls", folder])
+            .output()
+            .context("failed to run `pass ls`")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("not in the password store") {
+                return Ok(Vec::new());
+            }
+            bail!("`pass ls` failed ({}): {}", output.status, stderr.trim());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let names = stdout
+            .lines()
+            .skip(1) // first line is the folder name itself (tree root)
+            .filter_map(|line| {
+                let name = line.trim_start_matches(['|', '├', '└', '─', '│', ' ']);
+                if name.is_empty() { None } else { Some(name.to_string()) }
+            })
+            .collect();
+
+        Ok(names)
+    }
+
+    fn get_item(&self, name: &str, folder: &str) -> Result<Option<String>> {
+        let output = Command::new("pass")
+            .args(["show", &path_for(folder, name)])
+            .output()
+            .context("failed to run `pass show`")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if stderr.contains("not in the password store") {
+                return Ok(None);
+            }
+            bail!("`pass show` failed ({}): {}", output.status, stderr.trim());
+        }
+
+        Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+    }
+
+    fn create_item(&self, name: &str, folder: &str, notes_content: &str) -> Result<()> {
+        insert(&path_for(folder, name), notes_content)
+    }
+
+    fn edit_item(&self, name: &str, folder: &str, notes_content: &str) -> Result<()> {
+        insert(&path_for(folder, name), notes_content)
+    }
+
+    fn delete_item(&self, name: &str, folder: &str) -> Result<()> {
+        let output = Command::new("pass")
+            .args(["rm", "--force", &path_for(folder, name)])
+            .output()
+            .context("failed to run `pass rm`")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("`pass rm` failed ({}): {}", output.status, stderr.trim());
+        }
+        Ok(())
+    }
+}
+
+fn path_for(folder: &str, name: &str) -> String {
+    format!("{folder}/{name}")
+}
+
+/// Write (create or overwrite) an entry's body via `pass insert --multiline --force`,
+/// which reads the full body from stdin until EOF.
+fn insert(path: &str, notes_content: &str) -> Result<()> {
+    let mut child = Command::new("pass")
+        .args(["insert", "--multiline", "--force", path])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .context("failed to spawn pass")?;
+
+    child
+        .stdin
+        .take()
+        .context("failed to open pass stdin")?
+        .write_all(notes_content.as_bytes())
+        .context("failed to write to pass stdin")?;
+
+    let status = child.wait().context("failed to wait for pass")?;
+    if !status.success() {
+        bail!("pass exited with status {}", status);
+    }
+    Ok(())
+}
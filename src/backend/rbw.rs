@@ -0,0 +1,216 @@
+//! `SecretBackend` implementation driving the `rbw` CLI.
+//!
+//! Auth (unlock / login) is handled automatically by rbw itself — every rbw
+//! command runs `rbw unlock` / `rbw login` as needed before executing.  We
+//! just run the commands and propagate errors.
+//!
+//! Write strategy: pipe content directly to rbw's stdin.  When stdin is not a
+//! terminal, `rbw::edit::edit()` reads the entire stdin rather than launching
+//! an editor.  This avoids any temp-file / EDITOR tricks.
+
+use super::SecretBackend;
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use spinners::{Spinner, Spinners, Stream};
+use std::io::Write as _;
+use std::process::{Command, Stdio};
+
+// ── JSON shapes returned by `rbw list --raw` and `rbw get --raw` ─────────────
+
+#[derive(Debug, Deserialize)]
+struct ListItem {
+    name: String,
+    folder: Option<String>,
+    #[serde(rename = "type")]
+    #[allow(dead_code)]
+    item_type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RbwItem {
+    /// Entry type: "Login", "Note", etc.
+    #[serde(rename = "type")]
+    item_type: Option<String>,
+    notes: Option<String>,
+    fields: Option<Vec<RbwField>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RbwField {
+    name: String,
+    value: Option<String>,
+    #[serde(rename = "type")]
+    field_type: String,
+}
+
+/// Drives the real `rbw` CLI. The original, default backend.
+pub struct RbwBackend;
+
+impl SecretBackend for RbwBackend {
+    fn list_namespaces(&self, folder: &str) -> Result<Vec<String>> {
+        let mut sp = Spinner::with_stream(Spinners::Dots, "Fetching namespaces…".into(), Stream::Stderr);
+        let output = Command::new("rbw")
+            .args(["list", "--raw"])
+            .output()
+            .context("failed to run `rbw list`")?;
+        sp.stop_with_newline();
+
+        check_status("rbw list", &output)?;
+
+        let items: Vec<ListItem> = serde_json::from_slice(&output.stdout)
+            .context("failed to parse `rbw list --raw` output")?;
+
+        let names = items
+            .into_iter()
+            .filter(|i| i.folder.as_deref().unwrap_or("") == folder)
+            .map(|i| i.name)
+            .collect();
+
+        Ok(names)
+    }
+
+    fn get_item(&self, name: &str, folder: &str) -> Result<Option<String>> {
+        Ok(get_raw_item(name, folder)?.map(|item| resolve_notes(&item)))
+    }
+
+    /// Create a new entry (Login type) with `notes_content` in the given folder.
+    ///
+    /// `rbw add` always creates a Login entry.  When stdin is piped (not a TTY),
+    /// rbw reads the editor content directly from stdin.  Format: first line =
+    /// password (empty), rest = notes.
+    fn create_item(&self, name: &str, folder: &str, notes_content: &str) -> Result<()> {
+        // Prepend empty line so rbw's parse_editor treats it as an empty password.
+        let stdin_content = format!("\n{notes_content}\n");
+        pipe_to_rbw(&["add", "--folder", folder, name], &stdin_content)
+    }
+
+    /// Edit an existing entry, replacing its notes with `notes_content`.
+    ///
+    /// For Login entries (created by `create_item`): pipe `\n<content>` so the
+    /// first line (password) stays empty.
+    /// For SecureNote entries (envwarden-compatible): rbw internally prepends `\n`
+    /// before parsing, so pipe the content directly.
+    fn edit_item(&self, name: &str, folder: &str, notes_content: &str) -> Result<()> {
+        let is_secure_note = get_raw_item(name, folder)?
+            .and_then(|item| item.item_type)
+            .is_some_and(|t| t.eq_ignore_ascii_case("note"));
+
+        let stdin_content = if is_secure_note {
+            format!("{notes_content}\n")
+        } else {
+            format!("\n{notes_content}\n")
+        };
+        pipe_to_rbw(&["edit", "--folder", folder, name], &stdin_content)
+    }
+
+    fn delete_item(&self, name: &str, folder: &str) -> Result<()> {
+        let mut sp = Spinner::with_stream(Spinners::Dots, "Deleting from Bitwarden…".into(), Stream::Stderr);
+        let output = Command::new("rbw")
+            .args(["remove", "--folder", folder, name])
+            .output()
+            .context("failed to run `rbw remove`")?;
+        sp.stop_with_newline();
+        check_status("rbw remove", &output)
+    }
+}
+
+// ── Helpers ───────────────────────────────────────────────────────────────────
+
+/// Fetch a single item's notes and custom fields.
+/// Returns `None` if the item does not exist in the given folder.
+fn get_raw_item(name: &str, folder: &str) -> Result<Option<RbwItem>> {
+    let mut sp = Spinner::with_stream(Spinners::Dots, format!("Fetching '{name}'…"), Stream::Stderr);
+    let output = Command::new("rbw")
+        .args(["get", "--raw", "--folder", folder, name])
+        .output()
+        .context("failed to run `rbw get`")?;
+    sp.stop_with_newline();
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("no entry found")
+            || stderr.contains("no items found")
+            || stderr.contains("Entry not found")
+        {
+            return Ok(None);
+        }
+        bail!(
+            "`rbw get` failed ({}): {}",
+            output.status,
+            stderr.trim()
+        );
+    }
+
+    let item: RbwItem = serde_json::from_slice(&output.stdout)
+        .context("failed to parse `rbw get --raw` output")?;
+
+    Ok(Some(item))
+}
+
+/// Resolve an item's KEY=VALUE content: primarily the notes field, falling
+/// back to custom fields (envwarden compatibility, read-only) rendered as
+/// notes-style lines.
+fn resolve_notes(item: &RbwItem) -> String {
+    if let Some(notes) = &item.notes {
+        if !notes.is_empty() {
+            return notes.clone();
+        }
+    }
+
+    let mut lines = Vec::new();
+    if let Some(fields) = &item.fields {
+        for f in fields {
+            if matches!(f.field_type.as_str(), "text" | "hidden") {
+                if let Some(v) = &f.value {
+                    lines.push(format!("{}={}", f.name, v));
+                }
+            }
+        }
+    }
+    lines.join("\n")
+}
+
+/// Run an rbw command with the given args, piping `stdin_content` to its stdin.
+/// rbw's `edit::edit()` detects a non-TTY stdin and reads from it directly.
+///
+/// We also set `RBW_TTY` so the rbw-agent can use pinentry for unlock prompts
+/// even though our stdin is a pipe (not a terminal).
+fn pipe_to_rbw(args: &[&str], stdin_content: &str) -> Result<()> {
+    let mut cmd = Command::new("rbw");
+    cmd.args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit());
+
+    // Pass the controlling terminal so rbw-agent can launch pinentry even
+    // though our stdin is a pipe.  /dev/tty always refers to the ctty.
+    if std::path::Path::new("/dev/tty").exists() {
+        cmd.env("RBW_TTY", "/dev/tty");
+    }
+
+    let mut sp = Spinner::with_stream(Spinners::Dots, "Saving to Bitwarden…".into(), Stream::Stderr);
+    let mut child = cmd.spawn().context("failed to spawn rbw")?;
+
+    child
+        .stdin
+        .take()
+        .context("failed to open rbw stdin")?
+        .write_all(stdin_content.as_bytes())
+        .context("failed to write to rbw stdin")?;
+
+    let status = child.wait().context("failed to wait for rbw")?;
+    sp.stop_with_newline();
+    if !status.success() {
+        bail!("rbw exited with status {}", status);
+    }
+    Ok(())
+}
+
+/// Convert a failed `Command` output into an error message.
+fn check_status(cmd: &str, output: &std::process::Output) -> Result<()> {
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("`{}` failed ({}): {}", cmd, output.status, stderr.trim());
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+//! `~/.config/envrbw/config.toml` alias profiles.
+//!
+//! An alias bundles a backend, folder, and an ordered namespace list under a
+//! short name:
+//!
+//! ```toml
+//! [alias.deploy]
+//! folder = "work"
+//! namespaces = ["base", "prod"]
+//! ```
+//!
+//! so `envrbw @deploy mycmd` behaves like `envrbw --folder work base,prod mycmd`.
+//! Precedence when an alias is in play: CLI flag > alias entry > `ENVRBW_*`
+//! env var > built-in default.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub alias: HashMap<String, Alias>,
+}
+
+#[derive(Debug, Default, Deserialize, Clone)]
+pub struct Alias {
+    pub backend: Option<String>,
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub namespaces: Vec<String>,
+}
+
+/// Load `~/.config/envrbw/config.toml`, or an empty `Config` if it doesn't exist.
+pub fn load() -> Result<Config> {
+    let Some(path) = config_path() else {
+        return Ok(Config::default());
+    };
+    load_from(&path)
+}
+
+/// Load a `Config` from `path`, or an empty `Config` if it doesn't exist.
+/// Split out from `load()` so the parsing logic can be tested without
+/// depending on `$HOME`/`$XDG_CONFIG_HOME`.
+fn load_from(path: &std::path::Path) -> Result<Config> {
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let text = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    toml::from_str(&text).with_context(|| format!("failed to parse {}", path.display()))
+}
+
+fn config_path() -> Option<PathBuf> {
+    if let Ok(xdg) = std::env::var("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(xdg).join("envrbw").join("config.toml"));
+    }
+    let home = std::env::var("HOME").ok()?;
+    Some(PathBuf::from(home).join(".config").join("envrbw").join("config.toml"))
+}
+
+/// Strip a leading `@` from an alias reference (`@deploy` → `deploy`).
+/// Returns `None` if `raw` doesn't start with `@`, i.e. it isn't an alias.
+pub fn alias_name(raw: &str) -> Option<&str> {
+    raw.strip_prefix('@')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alias_name_strips_at() {
+        assert_eq!(alias_name("@deploy"), Some("deploy"));
+    }
+
+    #[test]
+    fn alias_name_rejects_bare_name() {
+        assert_eq!(alias_name("deploy"), None);
+    }
+
+    #[test]
+    fn load_from_missing_file_is_empty() {
+        let config = load_from(std::path::Path::new("/nonexistent/envrbw-test-config.toml")).unwrap();
+        assert!(config.alias.is_empty());
+    }
+
+    #[test]
+    fn load_from_malformed_file_errors() {
+        let path = write_temp_config("load_from_malformed_file_errors", "this is not valid toml [[[");
+        assert!(load_from(&path).is_err());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn load_from_parses_alias() {
+        let path = write_temp_config(
+            "load_from_parses_alias",
+            "[alias.deploy]\nfolder = \"work\"\nnamespaces = [\"base\", \"prod\"]",
+        );
+
+        let config = load_from(&path).unwrap();
+        let alias = &config.alias["deploy"];
+        assert_eq!(alias.folder.as_deref(), Some("work"));
+        assert_eq!(alias.namespaces, vec!["base", "prod"]);
+        let _ = std::fs::remove_file(path);
+    }
+
+    /// Write `contents` to a uniquely-named file under the system temp dir
+    /// and return its path, without pulling in a `tempfile` dependency just
+    /// for these tests.
+    fn write_temp_config(test_name: &str, contents: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("envrbw-test-config-{test_name}-{}.toml", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+}
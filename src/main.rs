@@ -1,8 +1,11 @@
-mod rbw;
+mod backend;
+mod config;
+mod picker;
 mod store;
 
 use anyhow::{Context, Result, bail};
-use clap::{CommandFactory, Parser, Subcommand};
+use backend::SecretBackend;
+use clap::{Parser, Subcommand};
 use rpassword::read_password;
 use std::collections::HashMap;
 use std::env;
@@ -24,11 +27,19 @@ struct Cli {
     #[arg(long, global = true, value_name = "FOLDER")]
     folder: Option<String>,
 
+    /// Secret store backend to use
+    /// [env: ENVRBW_BACKEND] [default: rbw] [possible values: rbw, pass, bw]
+    #[arg(long, global = true, value_name = "BACKEND")]
+    backend: Option<String>,
+
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// Namespace (for exec mode)
-    #[arg(value_name = "NAMESPACE")]
+    /// Namespace (for exec mode). Comma-separated to layer several
+    /// namespaces, later ones overriding earlier ones' keys. Or `@alias` to
+    /// use a profile from `~/.config/envrbw/config.toml`. Requires a command
+    /// to run; omit both to launch the interactive picker instead.
+    #[arg(value_name = "NAMESPACE", requires = "exec_command")]
     namespace: Option<String>,
 
     /// Command to execute (for exec mode)
@@ -63,29 +74,79 @@ enum Commands {
 
     /// List namespaces, or list keys in a namespace
     List {
-        /// Namespace to list keys from (lists all namespaces if omitted)
+        /// Namespace(s) to list keys from, comma-separated to layer several
+        /// (lists all namespaces if omitted)
         namespace: Option<String>,
 
         /// Show values alongside keys
         #[arg(short = 'v', long)]
         show_value: bool,
+
+        /// With --show-value, also show which namespace each value came from
+        #[arg(long, requires = "show_value")]
+        explain: bool,
     },
 
-    /// Remove keys from a namespace
+    /// Remove keys from a namespace, or delete the whole namespace
     Unset {
-        /// Namespace to remove keys from
+        /// Namespace to remove keys from (or delete, with --all)
         namespace: String,
 
         /// Environment variable names to remove
-        #[arg(required = true)]
+        #[arg(required_unless_present = "all")]
         vars: Vec<String>,
+
+        /// Delete the entire namespace instead of individual keys
+        #[arg(short = 'A', long, conflicts_with = "vars")]
+        all: bool,
     },
+
+    /// Print a namespace's pairs to stdout instead of exec'ing a child
+    Export {
+        /// Namespace(s) to export, comma-separated to layer several
+        namespace: String,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = ExportFormat::Sh)]
+        format: ExportFormat,
+    },
+
+    /// Interactively fuzzy-pick a namespace (and command or key)
+    Pick {
+        /// Command to exec with the picked namespace (omit to pick a key and print its value)
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        cmd: Vec<String>,
+    },
+
+    /// Print the resolved backend/folder/namespaces, optionally for an alias
+    Config {
+        /// Alias to resolve (`deploy` or `@deploy`); omit to show the default resolution
+        alias: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum ExportFormat {
+    /// `export KEY='value'` lines, for `eval "$(envrbw export ns)"`
+    Sh,
+    /// `set -gx KEY 'value'` lines, for fish's `eval`
+    Fish,
+    /// A single JSON object
+    Json,
+    /// `.env`-style `KEY=value` lines
+    Dotenv,
 }
 
 // ── Command implementations ────────────────────────────────────────────────────
 
-fn cmd_exec(folder: &str, namespace: &str, cmd: &str, args: &[String]) -> Result<()> {
-    let pairs = load_env_pairs(folder, namespace)?;
+fn cmd_exec(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespaces: &[String],
+    cmd: &str,
+    args: &[String],
+) -> Result<()> {
+    let pairs = load_merged(backend, folder, namespaces)?;
 
     // SAFETY: single-threaded at this point; no other thread reads the env.
     for (k, v) in &pairs {
@@ -109,9 +170,15 @@ fn cmd_exec(folder: &str, namespace: &str, cmd: &str, args: &[String]) -> Result
     }
 }
 
-fn cmd_set(folder: &str, namespace: &str, vars: &[String], noecho: bool) -> Result<()> {
+fn cmd_set(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespace: &str,
+    vars: &[String],
+    noecho: bool,
+) -> Result<()> {
     // Fetch existing content (or empty string for new namespace).
-    let existing_notes = existing_notes(folder, namespace)?;
+    let existing_notes = existing_notes(backend, folder, namespace)?;
 
     let mut notes = existing_notes.clone();
     for key in vars {
@@ -130,32 +197,42 @@ fn cmd_set(folder: &str, namespace: &str, vars: &[String], noecho: bool) -> Resu
         notes = store::update(&notes, key, &value);
     }
 
-    write_namespace(folder, namespace, &notes, existing_notes.is_empty())
+    write_namespace(backend, folder, namespace, &notes, existing_notes.is_empty())
 }
 
-fn cmd_list(folder: &str, namespace: Option<&str>, show_value: bool) -> Result<()> {
+fn cmd_list(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespace: Option<&str>,
+    show_value: bool,
+    explain: bool,
+) -> Result<()> {
     match namespace {
         None => {
-            let mut names = rbw::list_namespaces(folder)?;
+            let mut names = backend.list_namespaces(folder)?;
             names.sort();
             for name in names {
                 println!("{name}");
             }
         }
         Some(ns) => {
-            let pairs = load_env_pairs(folder, ns)?;
-            if pairs.is_empty() {
+            let namespaces = split_namespaces(ns);
+            let merged = load_merged_with_sources(backend, folder, &namespaces)?;
+            if merged.is_empty() {
                 eprintln!(
                     "WARNING: namespace `{ns}` not found or empty.\n\
                      You can set variables via: envrbw set {ns} SOME_VAR"
                 );
                 return Ok(());
             }
-            let mut keys: Vec<&String> = pairs.keys().collect();
+            let mut keys: Vec<&String> = merged.keys().collect();
             keys.sort();
             for key in keys {
-                if show_value {
-                    println!("{}={}", key, pairs[key]);
+                let (value, source) = &merged[key];
+                if show_value && explain {
+                    println!("{key}={value} (from {source})");
+                } else if show_value {
+                    println!("{key}={value}");
                 } else {
                     println!("{key}");
                 }
@@ -165,12 +242,22 @@ fn cmd_list(folder: &str, namespace: Option<&str>, show_value: bool) -> Result<(
     Ok(())
 }
 
-fn cmd_unset(folder: &str, namespace: &str, vars: &[String]) -> Result<()> {
-    let existing = existing_notes(folder, namespace)?;
+fn cmd_unset(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespace: &str,
+    vars: &[String],
+    all: bool,
+) -> Result<()> {
+    let existing = existing_notes(backend, folder, namespace)?;
     if existing.is_empty() {
         bail!("namespace `{namespace}` not found in folder `{folder}`");
     }
 
+    if all {
+        return backend.delete_item(namespace, folder);
+    }
+
     let mut notes = existing.clone();
     for key in vars {
         match store::remove(&notes, key) {
@@ -179,63 +266,207 @@ fn cmd_unset(folder: &str, namespace: &str, vars: &[String]) -> Result<()> {
         }
     }
 
-    write_namespace(folder, namespace, &notes, false)
+    write_namespace(backend, folder, namespace, &notes, false)
+}
+
+fn cmd_export(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespaces: &[String],
+    format: ExportFormat,
+) -> Result<()> {
+    let pairs = load_merged(backend, folder, namespaces)?;
+    let mut keys: Vec<&String> = pairs.keys().collect();
+    keys.sort();
+
+    match format {
+        ExportFormat::Sh => {
+            for key in keys {
+                println!("export {key}='{}'", sh_escape(&pairs[key]));
+            }
+        }
+        ExportFormat::Fish => {
+            for key in keys {
+                println!("set -gx {key} '{}'", sh_escape(&pairs[key]));
+            }
+        }
+        ExportFormat::Dotenv => {
+            println!("{}", store::serialize(&pairs));
+        }
+        ExportFormat::Json => {
+            println!("{}", serde_json::to_string(&pairs)?);
+        }
+    }
+    Ok(())
+}
+
+/// Escape a value for embedding in a POSIX/fish single-quoted string:
+/// replace every `'` with `'\''` and wrap the whole value in single quotes
+/// at the call site.
+fn sh_escape(value: &str) -> String {
+    value.replace('\'', r"'\''")
+}
+
+/// Fuzzy-pick a namespace, then either exec `cmd` in it (if given) or
+/// fuzzy-pick one of its keys and print its value.
+fn cmd_pick(backend: &dyn SecretBackend, folder: &str, cmd: &[String]) -> Result<()> {
+    let mut namespaces = backend.list_namespaces(folder)?;
+    namespaces.sort();
+
+    let Some(namespace) = picker::pick("namespace> ", &namespaces)? else {
+        return Ok(());
+    };
+
+    if let Some((prog, args)) = cmd.split_first() {
+        return cmd_exec(backend, folder, std::slice::from_ref(&namespace), prog, args);
+    }
+
+    let pairs = load_env_pairs(backend, folder, &namespace)?;
+    let mut keys: Vec<String> = pairs.keys().cloned().collect();
+    keys.sort();
+
+    let Some(key) = picker::pick(&format!("{namespace}> "), &keys)? else {
+        return Ok(());
+    };
+    println!("{}", pairs[&key]);
+    Ok(())
+}
+
+/// Print the resolved backend/folder/namespaces, optionally for a named alias.
+fn cmd_config(
+    config: &config::Config,
+    cli_folder: Option<&str>,
+    cli_backend: Option<&str>,
+    alias: Option<&str>,
+) -> Result<()> {
+    let alias_entry = match alias {
+        Some(raw) => {
+            let name = config::alias_name(raw).unwrap_or(raw);
+            Some(
+                config
+                    .alias
+                    .get(name)
+                    .with_context(|| format!("no alias `{name}` defined in config.toml"))?,
+            )
+        }
+        None => None,
+    };
+
+    let folder = resolve_folder(cli_folder, alias_entry.and_then(|a| a.folder.as_deref()));
+    let backend_name = cli_backend
+        .map(str::to_string)
+        .or_else(|| alias_entry.and_then(|a| a.backend.clone()))
+        .or_else(|| env::var(backend::BACKEND_ENV).ok())
+        .unwrap_or_else(|| backend::DEFAULT_BACKEND.to_string());
+
+    println!("backend: {backend_name}");
+    println!("folder: {folder}");
+    if let Some(namespaces) = alias_entry.map(|a| &a.namespaces).filter(|ns| !ns.is_empty()) {
+        println!("namespaces: {}", namespaces.join(","));
+    }
+    Ok(())
 }
 
 // ── Helpers ────────────────────────────────────────────────────────────────────
 
-/// Resolve the folder: CLI flag > env var > default.
-fn resolve_folder(cli_folder: Option<&str>) -> String {
+/// Resolve the folder: CLI flag > alias entry > env var > default.
+fn resolve_folder(cli_folder: Option<&str>, alias_folder: Option<&str>) -> String {
     cli_folder
         .map(str::to_string)
+        .or_else(|| alias_folder.map(str::to_string))
         .or_else(|| env::var(FOLDER_ENV).ok())
         .unwrap_or_else(|| DEFAULT_FOLDER.to_string())
 }
 
-/// Load env pairs for a namespace, merging notes-field KEY=VALUE lines and,
-/// as a fallback, any custom `fields[]` entries (envwarden compatibility).
-fn load_env_pairs(folder: &str, namespace: &str) -> Result<HashMap<String, String>> {
-    let item = rbw::get_item(namespace, folder)?
+/// Resolve the folder and backend for a non-alias-aware command (i.e.
+/// everything but exec mode and `config`, which resolve against an alias
+/// themselves). Done lazily per-command rather than once up front, so an
+/// invalid `--backend`/`ENVRBW_BACKEND` doesn't bail before `config` (which
+/// exists to debug exactly that) gets a chance to run.
+fn resolve_backend_and_folder(
+    cli_folder: Option<&str>,
+    cli_backend: Option<&str>,
+) -> Result<(String, Box<dyn SecretBackend>)> {
+    let folder = resolve_folder(cli_folder, None);
+    let backend = backend::resolve(cli_backend, None)?;
+    Ok((folder, backend))
+}
+
+/// Load env pairs for a namespace by parsing its backend's KEY=VALUE note content.
+fn load_env_pairs(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespace: &str,
+) -> Result<HashMap<String, String>> {
+    let notes = backend
+        .get_item(namespace, folder)?
         .with_context(|| format!("namespace `{namespace}` not found in folder `{folder}`"))?;
 
-    let mut pairs = HashMap::new();
+    Ok(store::parse(&notes))
+}
+
+/// Split a `NAMESPACE` argument on commas into an ordered layer list,
+/// trimming whitespace and dropping empty segments. Layering is comma-separated
+/// only (`base,prod`); passing the namespace argument multiple times is not
+/// supported, since that position is also how the trailing `exec_command`/
+/// `exec_args` are parsed.
+fn split_namespaces(raw: &str) -> Vec<String> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
 
-    // Primary: notes field KEY=VALUE lines.
-    if let Some(notes) = &item.notes {
-        pairs.extend(store::parse(notes));
+/// Fetch and merge several namespaces' pairs left-to-right, so later
+/// namespaces override earlier ones' keys. This is how `exec`/`export`
+/// support composable environment layers (base + per-environment overrides).
+fn load_merged(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespaces: &[String],
+) -> Result<HashMap<String, String>> {
+    let mut merged = HashMap::new();
+    for namespace in namespaces {
+        merged.extend(load_env_pairs(backend, folder, namespace)?);
     }
+    Ok(merged)
+}
 
-    // Fallback: custom fields (envwarden-compatible, read-only).
-    if pairs.is_empty() {
-        if let Some(fields) = &item.fields {
-            for f in fields {
-                if matches!(f.field_type.as_str(), "text" | "hidden") {
-                    if let Some(v) = &f.value {
-                        pairs.insert(f.name.clone(), v.clone());
-                    }
-                }
-            }
+/// Like `load_merged`, but also tracks which namespace contributed each
+/// key's final value, for `list --show-value --explain`.
+fn load_merged_with_sources(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespaces: &[String],
+) -> Result<HashMap<String, (String, String)>> {
+    let mut merged = HashMap::new();
+    for namespace in namespaces {
+        for (key, value) in load_env_pairs(backend, folder, namespace)? {
+            merged.insert(key, (value, namespace.clone()));
         }
     }
-
-    Ok(pairs)
+    Ok(merged)
 }
 
 /// Return the current notes content for a namespace, or an empty string if it
 /// does not yet exist.
-fn existing_notes(folder: &str, namespace: &str) -> Result<String> {
-    match rbw::get_item(namespace, folder)? {
-        Some(item) => Ok(item.notes.unwrap_or_default()),
-        None => Ok(String::new()),
-    }
+fn existing_notes(backend: &dyn SecretBackend, folder: &str, namespace: &str) -> Result<String> {
+    Ok(backend.get_item(namespace, folder)?.unwrap_or_default())
 }
 
 /// Write (create or edit) a namespace note.
-fn write_namespace(folder: &str, namespace: &str, notes: &str, is_new: bool) -> Result<()> {
+fn write_namespace(
+    backend: &dyn SecretBackend,
+    folder: &str,
+    namespace: &str,
+    notes: &str,
+    is_new: bool,
+) -> Result<()> {
     if is_new {
-        rbw::create_item(namespace, folder, notes)
+        backend.create_item(namespace, folder, notes)
     } else {
-        rbw::edit_item(namespace, folder, notes)
+        backend.edit_item(namespace, folder, notes)
     }
 }
 
@@ -250,7 +481,7 @@ fn main() {
 
 fn run() -> Result<()> {
     let cli = Cli::parse();
-    let folder = resolve_folder(cli.folder.as_deref());
+    let config = config::load()?;
 
     if let Some(command) = cli.command {
         match command {
@@ -258,19 +489,71 @@ fn run() -> Result<()> {
                 namespace,
                 vars,
                 noecho,
-            } => cmd_set(&folder, &namespace, &vars, noecho),
+            } => {
+                let (folder, backend) =
+                    resolve_backend_and_folder(cli.folder.as_deref(), cli.backend.as_deref())?;
+                cmd_set(backend.as_ref(), &folder, &namespace, &vars, noecho)
+            }
 
             Commands::List {
                 namespace,
                 show_value,
-            } => cmd_list(&folder, namespace.as_deref(), show_value),
+                explain,
+            } => {
+                let (folder, backend) =
+                    resolve_backend_and_folder(cli.folder.as_deref(), cli.backend.as_deref())?;
+                cmd_list(backend.as_ref(), &folder, namespace.as_deref(), show_value, explain)
+            }
+
+            Commands::Unset { namespace, vars, all } => {
+                let (folder, backend) =
+                    resolve_backend_and_folder(cli.folder.as_deref(), cli.backend.as_deref())?;
+                cmd_unset(backend.as_ref(), &folder, &namespace, &vars, all)
+            }
+
+            Commands::Export { namespace, format } => {
+                let (folder, backend) =
+                    resolve_backend_and_folder(cli.folder.as_deref(), cli.backend.as_deref())?;
+                cmd_export(backend.as_ref(), &folder, &split_namespaces(&namespace), format)
+            }
+
+            Commands::Pick { cmd } => {
+                let (folder, backend) =
+                    resolve_backend_and_folder(cli.folder.as_deref(), cli.backend.as_deref())?;
+                cmd_pick(backend.as_ref(), &folder, &cmd)
+            }
 
-            Commands::Unset { namespace, vars } => cmd_unset(&folder, &namespace, &vars),
+            // Deliberately doesn't call `backend::resolve`: this
+            // subcommand exists to debug a bad `--backend`/`ENVRBW_BACKEND`/alias
+            // combo, so it must still work when those would otherwise bail.
+            Commands::Config { alias } => {
+                cmd_config(&config, cli.folder.as_deref(), cli.backend.as_deref(), alias.as_deref())
+            }
         }
-    } else if let (Some(namespace), Some(command)) = (cli.namespace, cli.exec_command) {
-        cmd_exec(&folder, &namespace, &command, &cli.exec_args)
+    } else if let (Some(namespace_arg), Some(exec_command)) = (cli.namespace, cli.exec_command) {
+        let alias = match config::alias_name(&namespace_arg) {
+            Some(name) => Some(
+                config
+                    .alias
+                    .get(name)
+                    .with_context(|| format!("no alias `{name}` defined in config.toml"))?,
+            ),
+            None => None,
+        };
+        let folder = resolve_folder(cli.folder.as_deref(), alias.and_then(|a| a.folder.as_deref()));
+        let backend = backend::resolve(cli.backend.as_deref(), alias.and_then(|a| a.backend.as_deref()))?;
+        let namespaces = match alias {
+            Some(a) => a.namespaces.clone(),
+            None => split_namespaces(&namespace_arg),
+        };
+        cmd_exec(backend.as_ref(), &folder, &namespaces, &exec_command, &cli.exec_args)
     } else {
-        Cli::command().print_help().ok();
-        std::process::exit(2);
+        // `cli.namespace` is `None` here: `requires = "exec_command"` on the
+        // `namespace` arg means clap already rejects a namespace given without
+        // a command, so reaching this branch with a namespace typed but no
+        // command can't happen.
+        let (folder, backend) =
+            resolve_backend_and_folder(cli.folder.as_deref(), cli.backend.as_deref())?;
+        cmd_pick(backend.as_ref(), &folder, &[])
     }
 }
@@ -0,0 +1,262 @@
+//! Interactive fuzzy picker over namespaces and keys, used by the `pick`
+//! command and by bare `envrbw` invocations (no namespace given).
+//!
+//! Matching is a simple subsequence scorer: a candidate matches a query only
+//! if every query character appears in order (case-insensitively). Matches
+//! are ranked by a score that rewards consecutive runs and matches right
+//! after a separator (`_`, `.`, `-`), and penalizes gaps between matches.
+
+use anyhow::{Context, Result, bail};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::os::unix::io::AsRawFd;
+
+const SEPARATORS: [char; 3] = ['_', '.', '-'];
+const MAX_VISIBLE: usize = 10;
+
+/// Score `candidate` against `query`. Returns `None` if `query` is not a
+/// subsequence of `candidate` (case-insensitive). Higher is a better match.
+pub fn score(query: &str, candidate: &str) -> Option<i64> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let cand: Vec<char> = candidate.chars().collect();
+    let cand_lower: Vec<char> = candidate.to_lowercase().chars().collect();
+    let query_lower: Vec<char> = query.to_lowercase().chars().collect();
+
+    let mut total = 0i64;
+    let mut qi = 0;
+    let mut last_match: Option<usize> = None;
+
+    for (ci, &c) in cand_lower.iter().enumerate() {
+        if qi >= query_lower.len() {
+            break;
+        }
+        if c != query_lower[qi] {
+            continue;
+        }
+
+        total += 10;
+        match last_match {
+            Some(last) if ci == last + 1 => total += 15,
+            Some(last) => total -= (ci - last - 1) as i64,
+            None => {}
+        }
+        if ci > 0 && SEPARATORS.contains(&cand[ci - 1]) {
+            total += 10;
+        }
+        last_match = Some(ci);
+        qi += 1;
+    }
+
+    if qi != query_lower.len() {
+        return None;
+    }
+
+    // Normalize by candidate length: otherwise a longer candidate that
+    // merely matches right after a separator (e.g. "zz_db") can outscore a
+    // short exact match ("db") just by accumulating more per-match bonuses.
+    // Scale up first so integer division doesn't collapse close scores.
+    let len = cand_lower.len().max(1) as i64;
+    Some((total * 1000) / len)
+}
+
+/// Rank `candidates` against `query`, descending by score, dropping non-matches.
+fn filter_and_rank<'a>(query: &str, candidates: &'a [String]) -> Vec<&'a String> {
+    let mut scored: Vec<(i64, &String)> = candidates
+        .iter()
+        .filter_map(|c| score(query, c).map(|s| (s, c)))
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0));
+    scored.into_iter().map(|(_, c)| c).collect()
+}
+
+/// Drop into an interactive fuzzy finder over `candidates`, redrawing the
+/// filtered list on each keystroke. Returns the picked candidate, or `None`
+/// if the user cancelled (Esc/Ctrl-C).
+pub fn pick(prompt: &str, candidates: &[String]) -> Result<Option<String>> {
+    let mut tty = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open("/dev/tty")
+        .context("failed to open /dev/tty")?;
+
+    let raw = RawMode::enable(&tty)?;
+    let result = run_picker(&mut tty, prompt, candidates);
+    drop(raw);
+    writeln!(tty).ok();
+    result
+}
+
+fn run_picker(tty: &mut File, prompt: &str, candidates: &[String]) -> Result<Option<String>> {
+    let mut query = String::new();
+    let mut selected = 0usize;
+    let mut last_lines = 0usize;
+
+    loop {
+        let matches = filter_and_rank(&query, candidates);
+        if selected >= matches.len() {
+            selected = matches.len().saturating_sub(1);
+        }
+        last_lines = redraw(tty, prompt, &query, &matches, selected, last_lines)?;
+
+        match read_key(tty)? {
+            Key::Enter => return Ok(matches.get(selected).map(|s| (*s).clone())),
+            Key::Escape => return Ok(None),
+            Key::Up => selected = selected.saturating_sub(1),
+            Key::Down => {
+                if selected + 1 < matches.len() {
+                    selected += 1;
+                }
+            }
+            Key::Backspace => {
+                query.pop();
+            }
+            Key::Char(c) => {
+                query.push(c);
+                selected = 0;
+            }
+            Key::Ignored => {}
+        }
+    }
+}
+
+/// Redraw the prompt line plus up to `MAX_VISIBLE` candidates, erasing the
+/// previous frame first. Returns the number of lines drawn, so the caller can
+/// erase the same span next time.
+fn redraw(
+    tty: &mut File,
+    prompt: &str,
+    query: &str,
+    matches: &[&String],
+    selected: usize,
+    prev_lines: usize,
+) -> Result<usize> {
+    if prev_lines > 0 {
+        write!(tty, "\r\x1b[{prev_lines}A\x1b[J")?;
+    } else {
+        write!(tty, "\r\x1b[J")?;
+    }
+
+    writeln!(tty, "{prompt}{query}\r")?;
+    let shown = matches.len().min(MAX_VISIBLE);
+    for (i, m) in matches.iter().take(MAX_VISIBLE).enumerate() {
+        let marker = if i == selected { ">" } else { " " };
+        writeln!(tty, "{marker} {m}\r")?;
+    }
+    tty.flush()?;
+    Ok(shown + 1)
+}
+
+enum Key {
+    Enter,
+    Escape,
+    Up,
+    Down,
+    Backspace,
+    Char(char),
+    /// Recognized-but-irrelevant input (Left/Right arrows, a stray UTF-8
+    /// continuation byte, ...): redraw and keep waiting, don't cancel.
+    Ignored,
+}
+
+/// Read a single key from `tty`. Arrow keys arrive as a 3-byte escape
+/// sequence (`ESC [ A`/`ESC [ B`/`ESC [ C`/`ESC [ D`); anything else
+/// following a bare `ESC` is treated as Escape (cancel).
+fn read_key(tty: &mut File) -> Result<Key> {
+    let mut buf = [0u8; 1];
+    tty.read_exact(&mut buf).context("failed to read key")?;
+    match buf[0] {
+        b'\r' | b'\n' => Ok(Key::Enter),
+        0x03 => Ok(Key::Escape), // Ctrl-C
+        0x7f | 0x08 => Ok(Key::Backspace),
+        0x1b => {
+            let mut seq = [0u8; 2];
+            if tty.read_exact(&mut seq).is_err() {
+                return Ok(Key::Escape);
+            }
+            match seq {
+                [b'[', b'A'] => Ok(Key::Up),
+                [b'[', b'B'] => Ok(Key::Down),
+                [b'[', b'C' | b'D'] => Ok(Key::Ignored), // Right/Left: no cursor to move
+                _ => Ok(Key::Escape),
+            }
+        }
+        c if (c as char).is_ascii_graphic() || c == b' ' => Ok(Key::Char(c as char)),
+        // Non-ASCII bytes (e.g. UTF-8 continuation bytes of a multi-byte
+        // search term) aren't decoded into a Char here, but shouldn't cancel
+        // the session either.
+        _ => Ok(Key::Ignored),
+    }
+}
+
+/// RAII guard putting `/dev/tty` into raw mode, restoring the original
+/// termios settings on drop.
+struct RawMode {
+    fd: i32,
+    original: libc::termios,
+}
+
+impl RawMode {
+    fn enable(tty: &File) -> Result<Self> {
+        let fd = tty.as_raw_fd();
+        unsafe {
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(fd, &mut original) != 0 {
+                bail!("tcgetattr failed");
+            }
+            let mut raw = original;
+            libc::cfmakeraw(&mut raw);
+            if libc::tcsetattr(fd, libc::TCSANOW, &raw) != 0 {
+                bail!("tcsetattr failed");
+            }
+            Ok(Self { fd, original })
+        }
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(self.fd, libc::TCSANOW, &self.original);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subsequence_required() {
+        assert!(score("abc", "a_b_c").is_some());
+        assert!(score("abc", "acb").is_none());
+    }
+
+    #[test]
+    fn case_insensitive() {
+        assert!(score("ABC", "abc").is_some());
+    }
+
+    #[test]
+    fn consecutive_beats_scattered() {
+        let consecutive = score("ab", "ab_cd").unwrap();
+        let scattered = score("ab", "a_b_cd").unwrap();
+        assert!(consecutive > scattered);
+    }
+
+    #[test]
+    fn separator_boundary_is_rewarded() {
+        let boundary = score("db", "my_db_url").unwrap();
+        let mid_word = score("db", "my_adbcd").unwrap();
+        assert!(boundary > mid_word);
+    }
+
+    #[test]
+    fn ranking_sorts_descending() {
+        let candidates = vec!["zz_db".to_string(), "db_url".to_string(), "db".to_string()];
+        let ranked = filter_and_rank("db", &candidates);
+        assert_eq!(ranked[0], "db");
+    }
+}
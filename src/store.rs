@@ -1,34 +1,194 @@
 //! Parse and serialize KEY=VALUE pairs stored in a Bitwarden note's notes field.
+//!
+//! This is a small dotenv dialect, not naive `split_once('=')`: an optional
+//! leading `export `, double-quoted values with `\n`/`\t`/`\"`/`\\` escapes
+//! and `${OTHER_KEY}` interpolation, single-quoted values taken verbatim, and
+//! bare values otherwise. This lets values copied straight out of an
+//! existing `.env` file round-trip correctly.
 
 use std::collections::HashMap;
 
 /// Parse note content into a map of env-var key → value.
-/// - Splits on the **first** `=` only (values may contain `=`).
 /// - Skips blank lines and lines starting with `#`.
+/// - Strips an optional leading `export ` on each line.
+/// - A `"..."` value is double-quote-dialect: escapes and `${OTHER_KEY}`
+///   interpolation are processed, and the value may span multiple lines up
+///   to the matching unescaped closing quote.
+/// - A `'...'` value is taken verbatim, with no escaping or interpolation.
+/// - Otherwise the rest of the line, trimmed of trailing whitespace, is the
+///   raw value (values may contain `=`; only the first `=` ends the key).
 pub fn parse(notes: &str) -> HashMap<String, String> {
     let mut map = HashMap::new();
-    for line in notes.lines() {
-        let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
+    let chars: Vec<char> = notes.chars().collect();
+    let n = chars.len();
+    let mut i = 0;
+
+    while i < n {
+        while i < n && chars[i].is_whitespace() {
+            i += 1;
+        }
+        if i >= n {
+            break;
+        }
+
+        if chars[i] == '#' {
+            skip_to_eol(&chars, &mut i);
             continue;
         }
-        if let Some((k, v)) = line.split_once('=') {
-            map.insert(k.to_string(), v.to_string());
+
+        if starts_with_at(&chars, i, "export ") {
+            i += "export ".len();
+            while i < n && (chars[i] == ' ' || chars[i] == '\t') {
+                i += 1;
+            }
+        }
+
+        let key_start = i;
+        while i < n && chars[i] != '=' && chars[i] != '\n' {
+            i += 1;
+        }
+        if i >= n || chars[i] != '=' {
+            skip_to_eol(&chars, &mut i);
+            continue;
+        }
+        let key: String = chars[key_start..i].iter().collect::<String>().trim().to_string();
+        i += 1; // consume '='
+
+        let value = parse_value(&chars, &mut i, &map);
+        skip_to_eol(&chars, &mut i);
+
+        if !key.is_empty() {
+            map.insert(key, value);
         }
     }
+
     map
 }
 
-/// Serialize a map into sorted `KEY=VALUE` lines.
+fn parse_value(chars: &[char], i: &mut usize, already: &HashMap<String, String>) -> String {
+    let n = chars.len();
+    match chars.get(*i) {
+        Some('"') => {
+            *i += 1;
+            let mut raw = String::new();
+            while *i < n && chars[*i] != '"' {
+                if chars[*i] == '\\' && *i + 1 < n {
+                    match chars[*i + 1] {
+                        'n' => raw.push('\n'),
+                        't' => raw.push('\t'),
+                        '"' => raw.push('"'),
+                        '\\' => raw.push('\\'),
+                        other => {
+                            raw.push('\\');
+                            raw.push(other);
+                        }
+                    }
+                    *i += 2;
+                } else {
+                    raw.push(chars[*i]);
+                    *i += 1;
+                }
+            }
+            if *i < n {
+                *i += 1; // consume closing quote
+            }
+            interpolate(&raw, already)
+        }
+        Some('\'') => {
+            *i += 1;
+            let mut raw = String::new();
+            while *i < n && chars[*i] != '\'' {
+                raw.push(chars[*i]);
+                *i += 1;
+            }
+            if *i < n {
+                *i += 1; // consume closing quote
+            }
+            raw
+        }
+        _ => {
+            let start = *i;
+            while *i < n && chars[*i] != '\n' {
+                *i += 1;
+            }
+            chars[start..*i].iter().collect::<String>().trim_end().to_string()
+        }
+    }
+}
+
+/// Expand `${OTHER_KEY}` references against keys already parsed earlier in
+/// the same note, falling back to the process environment. An unresolved
+/// reference (undefined, or a forward/cyclic reference not yet parsed) is
+/// left as a literal `${OTHER_KEY}` rather than looping.
+fn interpolate(value: &str, already: &HashMap<String, String>) -> String {
+    let chars: Vec<char> = value.chars().collect();
+    let n = chars.len();
+    let mut out = String::new();
+    let mut i = 0;
+
+    while i < n {
+        if chars[i] == '$' && chars.get(i + 1) == Some(&'{') {
+            if let Some(len) = chars[i + 2..].iter().position(|&c| c == '}') {
+                let key: String = chars[i + 2..i + 2 + len].iter().collect();
+                match already.get(&key).cloned().or_else(|| std::env::var(&key).ok()) {
+                    Some(v) => out.push_str(&v),
+                    None => out.push_str(&format!("${{{key}}}")),
+                }
+                i += 2 + len + 1;
+                continue;
+            }
+        }
+        out.push(chars[i]);
+        i += 1;
+    }
+
+    out
+}
+
+fn starts_with_at(chars: &[char], i: usize, s: &str) -> bool {
+    s.chars().enumerate().all(|(j, c)| chars.get(i + j) == Some(&c))
+}
+
+fn skip_to_eol(chars: &[char], i: &mut usize) {
+    while *i < chars.len() && chars[*i] != '\n' {
+        *i += 1;
+    }
+}
+
+/// Serialize a map into sorted `KEY=VALUE` lines, double-quoting (and
+/// escaping) values that contain whitespace, `#`, `=`, a quote, or a
+/// newline; simple values are left bare.
 pub fn serialize(pairs: &HashMap<String, String>) -> String {
     let mut keys: Vec<&String> = pairs.keys().collect();
     keys.sort();
     keys.iter()
-        .map(|k| format!("{}={}", k, pairs[*k]))
+        .map(|k| format!("{}={}", k, serialize_value(&pairs[*k])))
         .collect::<Vec<_>>()
         .join("\n")
 }
 
+fn serialize_value(value: &str) -> String {
+    let needs_quotes = value
+        .chars()
+        .any(|c| c.is_whitespace() || matches!(c, '#' | '=' | '"' | '\''));
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '\\' => out.push_str("\\\\"),
+            '"' => out.push_str("\\\""),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            other => out.push(other),
+        }
+    }
+    out.push('"');
+    out
+}
+
 /// Upsert a single key in existing note content, preserving other lines.
 pub fn update(existing: &str, key: &str, value: &str) -> String {
     let mut pairs = parse(existing);
@@ -92,4 +252,71 @@ mod tests {
     fn remove_missing_returns_none() {
         assert!(remove("A=1", "MISSING").is_none());
     }
+
+    #[test]
+    fn strips_export_prefix() {
+        let m = parse("export A=1\nexport B=\"two\"\n");
+        assert_eq!(m["A"], "1");
+        assert_eq!(m["B"], "two");
+    }
+
+    #[test]
+    fn double_quoted_escapes() {
+        let m = parse(r#"A="line1\nline2\ttabbed\"quoted\\slash""#);
+        assert_eq!(m["A"], "line1\nline2\ttabbed\"quoted\\slash");
+    }
+
+    #[test]
+    fn double_quoted_value_spans_multiple_lines() {
+        let m = parse("A=\"line1\nline2\"\nB=2\n");
+        assert_eq!(m["A"], "line1\nline2");
+        assert_eq!(m["B"], "2");
+    }
+
+    #[test]
+    fn single_quoted_is_verbatim() {
+        let m = parse(r#"A='$\{NOT_EXPANDED} \n literal'"#);
+        assert_eq!(m["A"], r"$\{NOT_EXPANDED} \n literal");
+    }
+
+    #[test]
+    fn interpolates_already_parsed_keys() {
+        let m = parse("HOST=\"db\"\nURL=\"postgres://${HOST}/app\"\n");
+        assert_eq!(m["URL"], "postgres://db/app");
+    }
+
+    #[test]
+    fn unresolved_interpolation_left_literal() {
+        let m = parse("A=\"${UNDEFINED_KEY}\"\n");
+        assert_eq!(m["A"], "${UNDEFINED_KEY}");
+    }
+
+    #[test]
+    fn cyclic_interpolation_does_not_loop() {
+        // B references A, but A is defined *after* B, so at the time B is
+        // parsed A isn't in `already` yet: B resolves to the literal "${A}".
+        // A is then parsed with B available, so `${B}` substitutes to B's
+        // already-resolved value — the literal string "${A}" — not a fresh
+        // lookup of A. Either way nothing loops.
+        let m = parse("B=\"${A}\"\nA=\"${B}\"\n");
+        assert_eq!(m["B"], "${A}");
+        assert_eq!(m["A"], "${A}");
+    }
+
+    #[test]
+    fn serialize_quotes_values_with_whitespace() {
+        let mut pairs = HashMap::new();
+        pairs.insert("A".to_string(), "has space".to_string());
+        assert_eq!(serialize(&pairs), "A=\"has space\"");
+
+        let m = parse(&serialize(&pairs));
+        assert_eq!(m["A"], "has space");
+    }
+
+    #[test]
+    fn serialize_leaves_simple_values_bare() {
+        let mut pairs = HashMap::new();
+        pairs.insert("A".to_string(), "simple".to_string());
+        assert_eq!(serialize(&pairs), "A=simple");
+    }
 }